@@ -1,16 +1,29 @@
-use long_running_task::{Progressible, TaskPool, TaskState};
+use long_running_task::{Executor, Progressible, TaskPool, TaskState};
 use rocket::{
     get, launch, post, routes,
     serde::{json::Json, uuid::Uuid, Serialize},
     State,
 };
 use std::{
+    future::Future,
     sync::{Arc, Mutex},
     time::Duration,
 };
 use tokio::{task, time::sleep};
 
-#[derive(Serialize)]
+/// Drives spawned task futures on the ambient tokio runtime.
+struct TokioExecutor;
+
+impl Executor for TokioExecutor {
+    fn execute<Fut>(&self, future: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        task::spawn(future);
+    }
+}
+
+#[derive(Clone, Serialize)]
 #[serde(crate = "rocket::serde")]
 struct Response(usize);
 
@@ -29,23 +42,23 @@ impl Progressible for Progress {
 
 /// curl -X POST http://localhost:8000
 #[post("/")]
-fn start_task(task_pool: &State<Arc<Mutex<TaskPool<Response, Progress>>>>) -> String {
+fn start_task(task_pool: &State<Arc<Mutex<TaskPool<Response, Progress, String>>>>) -> String {
     let total = 10;
-    let (handle, uuid) = task_pool
-        .lock()
-        .unwrap()
-        .insert(Progress { progress: 0, total });
-
     let task_pool = Arc::clone(task_pool);
 
-    task::spawn(async move {
-        for _ in 0..total {
-            sleep(Duration::from_millis(1_000)).await;
-            task_pool.lock().unwrap().progress(&handle);
-        }
+    let uuid = TaskPool::spawn(
+        &task_pool,
+        &TokioExecutor,
+        Progress { progress: 0, total },
+        move |reporter| async move {
+            for _ in 0..total {
+                sleep(Duration::from_millis(1_000)).await;
+                reporter.progress();
+            }
 
-        task_pool.lock().unwrap().complete(handle, Response(42));
-    });
+            Ok(Response(42))
+        },
+    );
 
     uuid.to_string()
 }
@@ -54,15 +67,16 @@ fn start_task(task_pool: &State<Arc<Mutex<TaskPool<Response, Progress>>>>) -> St
 #[get("/<uuid>")]
 fn get_task(
     uuid: Uuid,
-    task_pool: &State<Arc<Mutex<TaskPool<Response, Progress>>>>,
-) -> Option<Json<TaskState<Response, Progress>>> {
+    task_pool: &State<Arc<Mutex<TaskPool<Response, Progress, String>>>>,
+) -> Option<Json<TaskState<Response, Progress, String>>> {
     task_pool.lock().unwrap().retrieve(&uuid).map(Json)
 }
 
 #[launch]
 fn rocket() -> _ {
     let task_pool =
-        TaskPool::<Response, Progress>::default().with_lifespan(Some(Duration::from_secs(60)));
+        TaskPool::<Response, Progress, String>::default()
+            .with_lifespan(Some(Duration::from_secs(60)));
 
     rocket::build()
         .mount("/", routes![start_task, get_task])
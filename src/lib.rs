@@ -6,8 +6,19 @@
 
 use std::{
     collections::HashMap,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
     time::{Duration, Instant},
 };
+
+#[cfg(feature = "lifespan")]
+use std::thread;
 use uuid::Uuid;
 
 /// Structs implementing this trait hold the current progress of a task.
@@ -16,10 +27,10 @@ pub trait Progressible {
     fn progress(&mut self);
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// Representation of a task's state.
-pub enum TaskState<V, P>
+pub enum TaskState<V, P, E>
 where
     P: Progressible,
 {
@@ -27,26 +38,145 @@ where
     Pending(P),
     /// The task is done with a value of type V.
     Done(V),
+    /// The task failed with an error of type E.
+    Failed(E),
+    /// The task was cancelled before it finished.
+    Cancelled,
 }
 
-/// A pool to manage long-running tasks.
-pub struct TaskPool<V, P>
+/// A pluggable storage backend for a [`TaskPool`].
+///
+/// The default [`InMemoryStore`] keeps all task state in [`HashMap`]s, so it is
+/// lost on restart and cannot be shared between server instances. Implement this
+/// trait to back a pool with durable storage instead — for example a table keyed
+/// by `Uuid` that (de)serialises `P`, `V` and `E` through the crate's `serde`
+/// feature, so that pending and terminal tasks survive a restart and workers can
+/// recover or scale horizontally. Such a Postgres/Diesel-backed store is expected
+/// to live behind its own feature flag.
+pub trait TaskStore<V, P, E>
 where
-    P: Progressible,
+    P: Progressible + Clone,
 {
+    /// Store the initial progress of a freshly inserted task.
+    fn put_pending(&mut self, uuid: Uuid, pending: P);
+    /// Get a clone of a pending task's progress, or `None` if it is not pending.
+    fn get(&self, uuid: &Uuid) -> Option<P>;
+    /// Advance a pending task's progress via [`Progressible::progress`] and return
+    /// the updated progress, or `None` if the task is not pending.
+    fn set_progress(&mut self, uuid: &Uuid) -> Option<P>;
+    /// Move a pending task into the completed set, stamped with `at`.
+    fn move_to_completed(&mut self, uuid: Uuid, value: V, at: Instant);
+    /// Move a pending task into the failed set, stamped with `at`.
+    fn move_to_failed(&mut self, uuid: Uuid, error: E, at: Instant);
+    /// Remove and return a completed task's value, if present.
+    fn take_completed(&mut self, uuid: &Uuid) -> Option<V>;
+    /// Remove and return a failed task's error, if present.
+    fn take_failed(&mut self, uuid: &Uuid) -> Option<E>;
+    /// Move a pending task into the cancelled set, stamped with `at`.
+    fn move_to_cancelled(&mut self, uuid: Uuid, at: Instant);
+    /// Remove a cancelled task, returning whether one was present.
+    fn take_cancelled(&mut self, uuid: &Uuid) -> bool;
+    /// Drop every terminal (completed, failed or cancelled) task older than `lifespan`.
+    fn purge_expired(&mut self, lifespan: Duration);
+}
+
+/// The default in-memory [`TaskStore`], backing a pool with [`HashMap`]s.
+pub struct InMemoryStore<V, P, E> {
     pending: HashMap<Uuid, P>,
     completed: HashMap<Uuid, (Instant, V)>,
+    failed: HashMap<Uuid, (Instant, E)>,
+    cancelled: HashMap<Uuid, Instant>,
+}
+
+impl<V, P, E> Default for InMemoryStore<V, P, E> {
+    fn default() -> Self {
+        Self {
+            pending: HashMap::new(),
+            completed: HashMap::new(),
+            failed: HashMap::new(),
+            cancelled: HashMap::new(),
+        }
+    }
+}
+
+impl<V, P, E> TaskStore<V, P, E> for InMemoryStore<V, P, E>
+where
+    P: Progressible + Clone,
+{
+    fn put_pending(&mut self, uuid: Uuid, pending: P) {
+        self.pending.insert(uuid, pending);
+    }
+
+    fn get(&self, uuid: &Uuid) -> Option<P> {
+        self.pending.get(uuid).cloned()
+    }
+
+    fn set_progress(&mut self, uuid: &Uuid) -> Option<P> {
+        let p = self.pending.get_mut(uuid)?;
+        p.progress();
+        Some(p.clone())
+    }
+
+    fn move_to_completed(&mut self, uuid: Uuid, value: V, at: Instant) {
+        self.pending.remove(&uuid);
+        self.completed.insert(uuid, (at, value));
+    }
+
+    fn move_to_failed(&mut self, uuid: Uuid, error: E, at: Instant) {
+        self.pending.remove(&uuid);
+        self.failed.insert(uuid, (at, error));
+    }
+
+    fn take_completed(&mut self, uuid: &Uuid) -> Option<V> {
+        self.completed.remove(uuid).map(|(_, value)| value)
+    }
+
+    fn take_failed(&mut self, uuid: &Uuid) -> Option<E> {
+        self.failed.remove(uuid).map(|(_, error)| error)
+    }
+
+    fn move_to_cancelled(&mut self, uuid: Uuid, at: Instant) {
+        self.pending.remove(&uuid);
+        self.cancelled.insert(uuid, at);
+    }
+
+    fn take_cancelled(&mut self, uuid: &Uuid) -> bool {
+        self.cancelled.remove(uuid).is_some()
+    }
+
+    fn purge_expired(&mut self, lifespan: Duration) {
+        let now = Instant::now();
+        self.completed
+            .retain(|_, (inserted_at, _)| now.duration_since(*inserted_at) < lifespan);
+        self.failed
+            .retain(|_, (inserted_at, _)| now.duration_since(*inserted_at) < lifespan);
+        self.cancelled
+            .retain(|_, inserted_at| now.duration_since(*inserted_at) < lifespan);
+    }
+}
+
+/// The senders registered against a task via [`TaskPool::subscribe`].
+type Subscribers<V, P, E> = HashMap<Uuid, Vec<Sender<TaskState<V, P, E>>>>;
+
+/// A pool to manage long-running tasks.
+pub struct TaskPool<V, P, E, S = InMemoryStore<V, P, E>>
+where
+    P: Progressible,
+{
+    store: S,
+    subscribers: Subscribers<V, P, E>,
     lifespan: Option<Duration>,
 }
 
-impl<V, P> Default for TaskPool<V, P>
+impl<V, P, E, S> Default for TaskPool<V, P, E, S>
 where
     P: Progressible,
+    S: Default,
 {
     fn default() -> Self {
         Self {
-            pending: HashMap::new(),
-            completed: HashMap::new(),
+            store: S::default(),
+            subscribers: HashMap::new(),
             lifespan: None,
         }
     }
@@ -59,9 +189,51 @@ pub struct Handle {
     uuid: Uuid,
 }
 
-impl<V, P> TaskPool<V, P>
+/// A cloneable progress reporter handed to work spawned via [`TaskPool::spawn`].
+/// It wraps a shared reference to the owning pool together with the task's `Uuid`,
+/// so the spawned closure can report progress without threading a [`Handle`] or
+/// the pool through by hand. The pool retains the [`Handle`] and completes or fails
+/// the task itself once the future returned by the closure resolves.
+pub struct Reporter<V, P, E, S = InMemoryStore<V, P, E>>
+where
+    P: Progressible,
+{
+    pool: Arc<Mutex<TaskPool<V, P, E, S>>>,
+    uuid: Uuid,
+}
+
+impl<V, P, E, S> Clone for Reporter<V, P, E, S>
+where
+    P: Progressible,
+{
+    fn clone(&self) -> Self {
+        Self {
+            pool: Arc::clone(&self.pool),
+            uuid: self.uuid,
+        }
+    }
+}
+
+impl<V, P, E, S> Reporter<V, P, E, S>
 where
     P: Progressible + Clone,
+    V: Clone,
+    E: Clone,
+    S: TaskStore<V, P, E>,
+{
+    /// Report progress on the spawned task, pushing the new progress to any
+    /// subscribers. Does nothing if the task is no longer pending.
+    pub fn progress(&self) {
+        if let Ok(mut pool) = self.pool.lock() {
+            pool.report_progress(&self.uuid);
+        }
+    }
+}
+
+impl<V, P, E, S> TaskPool<V, P, E, S>
+where
+    P: Progressible + Clone,
+    S: TaskStore<V, P, E>,
 {
     /// Configure the lifespan of tasks.
     /// `None` means that tasks will never expire.
@@ -80,27 +252,94 @@ where
     #[must_use]
     pub fn insert(&mut self, pending: P) -> (Handle, Uuid) {
         let uuid = Uuid::new_v4();
-        self.pending.insert(uuid, pending);
+        self.store.put_pending(uuid, pending);
         (Handle { uuid }, uuid)
     }
 
     /// Get the task state and remove it from the pool if it is done.
-    pub fn retrieve(&mut self, uuid: &Uuid) -> Option<TaskState<V, P>> {
-        use TaskState::{Done, Pending};
+    pub fn retrieve(&mut self, uuid: &Uuid) -> Option<TaskState<V, P, E>> {
+        use TaskState::{Cancelled, Done, Failed, Pending};
+
+        if let Some(p) = self.store.get(uuid) {
+            return Some(Pending(p));
+        }
 
-        if let Some(p) = self.pending.get(uuid) {
-            return Some(Pending(p.clone()));
+        if let Some(value) = self.store.take_completed(uuid) {
+            return Some(Done(value));
         }
 
-        self.completed.remove(uuid).map(|f| Done(f.1))
+        if let Some(error) = self.store.take_failed(uuid) {
+            return Some(Failed(error));
+        }
+
+        self.store.take_cancelled(uuid).then_some(Cancelled)
+    }
+
+    /// Cancel a pending task, recording a terminal [`TaskState::Cancelled`] state
+    /// that a subsequent `retrieve` returns once and then clears. The handle must
+    /// be passed by value so that cancellation is final and race-free.
+    /// As a side effect expired tasks are purged.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn cancel(&mut self, handle: Handle) {
+        self.notify_cancelled(&handle.uuid);
+        self.subscribers.remove(&handle.uuid);
+        self.purge_expired_tasks();
+        self.store.move_to_cancelled(handle.uuid, Instant::now());
+    }
+
+    /// Push the terminal [`TaskState::Cancelled`] to every subscriber of `uuid`,
+    /// dropping senders whose receiver has already been dropped. Unlike `notify`
+    /// this needs no `V: Clone`/`E: Clone` bound because the variant carries no
+    /// payload, so it can live on the core impl alongside `cancel`.
+    fn notify_cancelled(&mut self, uuid: &Uuid) {
+        if let Some(senders) = self.subscribers.get_mut(uuid) {
+            senders.retain(|sender| sender.send(TaskState::Cancelled).is_ok());
+        }
+    }
+
+    fn purge_expired_tasks(&mut self) {
+        if let Some(lifespan) = self.lifespan {
+            self.store.purge_expired(lifespan);
+        }
+    }
+}
+
+impl<V, P, E, S> TaskPool<V, P, E, S>
+where
+    P: Progressible + Clone,
+    V: Clone,
+    E: Clone,
+    S: TaskStore<V, P, E>,
+{
+    /// Subscribe to state changes of a task, returning a [`Receiver`] that
+    /// yields the task's current progress on every `progress` call and its
+    /// terminal state once `complete`, `fail` or `cancel` is invoked.
+    /// Returns `None` if the task is unknown (for example already terminal).
+    /// Multiple subscribers can be registered against the same task.
+    pub fn subscribe(&mut self, uuid: &Uuid) -> Option<Receiver<TaskState<V, P, E>>> {
+        self.store.get(uuid)?;
+
+        let (sender, receiver) = channel();
+        self.subscribers.entry(*uuid).or_default().push(sender);
+        Some(receiver)
     }
 
     /// Report progress on a pending task.
     /// Calls `Progressible::progress` on the corresponding progress state.
+    /// The updated progress is pushed to all registered subscribers.
     pub fn progress(&mut self, handle: &Handle) {
-        match self.pending.get_mut(&handle.uuid) {
-            Some(p) => p.progress(),
-            None => unreachable!("Pending task not found. This should never happen because a task's handle cannot outlive the task."),
+        let Some(progress) = self.store.set_progress(&handle.uuid) else {
+            unreachable!("Pending task not found. This should never happen because a task's handle cannot outlive the task.");
+        };
+
+        self.notify(&handle.uuid, &TaskState::Pending(progress));
+    }
+
+    /// Push a state to every subscriber of `uuid`, dropping senders whose
+    /// receiver has already been dropped.
+    fn notify(&mut self, uuid: &Uuid, state: &TaskState<V, P, E>) {
+        if let Some(senders) = self.subscribers.get_mut(uuid) {
+            senders.retain(|sender| sender.send(state.clone()).is_ok());
         }
     }
 
@@ -109,20 +348,155 @@ where
     /// As a side effect expired tasks are purged.
     #[allow(clippy::needless_pass_by_value)]
     pub fn complete(&mut self, handle: Handle, value: V) {
-        self.pending.remove(&handle.uuid);
+        self.notify(&handle.uuid, &TaskState::Done(value.clone()));
+        self.subscribers.remove(&handle.uuid);
         self.purge_expired_tasks();
-        self.completed.insert(handle.uuid, (Instant::now(), value));
+        self.store
+            .move_to_completed(handle.uuid, value, Instant::now());
     }
 
-    fn purge_expired_tasks(&mut self) {
-        if let Some(lifespan) = self.lifespan {
-            let now = Instant::now();
-            self.completed
-                .retain(|_, (inserted_at, _)| now.duration_since(*inserted_at) < lifespan);
+    /// Mark the task associated to the handle as failed with an error of type E.
+    /// The handle must be passed by value so that this is the final action.
+    /// As a side effect expired tasks are purged.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn fail(&mut self, handle: Handle, error: E) {
+        self.notify(&handle.uuid, &TaskState::Failed(error.clone()));
+        self.subscribers.remove(&handle.uuid);
+        self.purge_expired_tasks();
+        self.store.move_to_failed(handle.uuid, error, Instant::now());
+    }
+
+    /// Advance a task's progress by its `Uuid` on behalf of a [`Reporter`].
+    /// Unlike `progress` this is forgiving: a task that has already reached a
+    /// terminal state is silently ignored rather than panicking, because a
+    /// `Reporter` clone may outlive the task.
+    fn report_progress(&mut self, uuid: &Uuid) {
+        if let Some(progress) = self.store.set_progress(uuid) {
+            self.notify(uuid, &TaskState::Pending(progress));
         }
     }
 }
 
+/// An executor capable of driving a spawned task's future to completion.
+///
+/// Implement this for your async runtime so a [`TaskPool`] can drive
+/// self-spawned work on it — for example a thin wrapper around
+/// `tokio::spawn`. Keeping the executor pluggable lets the pool integrate with
+/// whatever runtime the caller already uses instead of imposing one.
+pub trait Executor {
+    /// Drive `future` to completion on this executor.
+    fn execute<Fut>(&self, future: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static;
+}
+
+/// Wraps a task future so that a panic raised while polling it is caught and
+/// turned into `Err(E::default())` instead of unwinding through the executor.
+/// Without this a panicking task future would leave the task `Pending` forever;
+/// catching it here lets [`TaskPool::spawn`] fail the task regardless of the
+/// executor's own panic policy.
+struct CatchPanic<Fut> {
+    inner: Fut,
+}
+
+impl<Fut, V, E> Future for CatchPanic<Fut>
+where
+    Fut: Future<Output = Result<V, E>>,
+    E: Default,
+{
+    type Output = Result<V, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `inner` is never moved out of `self`; we only project a pinned
+        // reference to it, upholding the pinning invariant.
+        let inner = unsafe { self.map_unchecked_mut(|s| &mut s.inner) };
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(poll) => poll,
+            Err(_) => Poll::Ready(Err(E::default())),
+        }
+    }
+}
+
+impl<V, P, E, S> TaskPool<V, P, E, S>
+where
+    P: Progressible + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+    E: Clone + Default + Send + 'static,
+    S: TaskStore<V, P, E> + Send + 'static,
+{
+    /// Spawn a unit of work that drives itself: insert the `initial` progress,
+    /// hand the future returned by `f` to `executor`, and `complete` or `fail`
+    /// the task automatically once that future resolves — collapsing the manual
+    /// insert → loop `progress` → `complete` boilerplate into a single call.
+    ///
+    /// `f` receives a cloneable [`Reporter`] it can use to report progress and
+    /// returns a future resolving to `Result<V, E>`. If the future resolves to
+    /// `Ok(value)` the task is completed with that value; if it resolves to
+    /// `Err(error)` the task is failed with that error. If the future instead
+    /// **panics** while being polled the panic is caught and the task is failed
+    /// with `E::default()`, so a panicking task never hangs the client in a
+    /// poll loop. This is why `E` must be `Default` here. The `Uuid` of the new
+    /// task is returned immediately so callers can poll or `subscribe` to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool's mutex is poisoned — i.e. another thread panicked
+    /// while holding the lock.
+    pub fn spawn<F, Fut, Ex>(pool: &Arc<Mutex<Self>>, executor: &Ex, initial: P, f: F) -> Uuid
+    where
+        F: FnOnce(Reporter<V, P, E, S>) -> Fut,
+        Fut: Future<Output = Result<V, E>> + Send + 'static,
+        Ex: Executor,
+    {
+        let (handle, uuid) = pool.lock().unwrap().insert(initial);
+        let reporter = Reporter {
+            pool: Arc::clone(pool),
+            uuid,
+        };
+        let driver = Arc::clone(pool);
+        let future = CatchPanic { inner: f(reporter) };
+
+        executor.execute(async move {
+            let result = future.await;
+            let mut pool = driver.lock().unwrap();
+            match result {
+                Ok(value) => pool.complete(handle, value),
+                Err(error) => pool.fail(handle, error),
+            }
+        });
+
+        uuid
+    }
+}
+
+#[cfg(feature = "lifespan")]
+impl<V, P, E, S> TaskPool<V, P, E, S>
+where
+    P: Progressible + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+    S: TaskStore<V, P, E> + Send + 'static,
+{
+    /// Spawn a background reaper thread that purges expired terminal tasks on an
+    /// interval timer, so lifespan enforcement no longer depends on new
+    /// completions arriving. Each `interval` tick it takes the lock and drops
+    /// every completed, failed or cancelled task older than the configured lifespan; if no
+    /// lifespan is configured the tick is a no-op. The returned join handle lives
+    /// for as long as the reaper loops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool's mutex is poisoned — i.e. another thread panicked
+    /// while holding the lock.
+    #[must_use]
+    pub fn spawn_reaper(pool: Arc<Mutex<Self>>, interval: Duration) -> thread::JoinHandle<()> {
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            pool.lock().unwrap().purge_expired_tasks();
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[derive(Clone, Debug, PartialEq, Eq)]
@@ -147,11 +521,11 @@ mod tests {
     use std::{thread, time::Duration};
 
     use super::Progressible;
-    use crate::{TaskPool, TaskState::*};
+    use crate::{InMemoryStore, TaskPool, TaskState::*};
 
     #[test]
     fn insert_and_get() {
-        let mut pool = TaskPool::<u8, Progress>::default();
+        let mut pool = TaskPool::<u8, Progress, ()>::default();
         let initial_value = Progress {
             progress: 0,
             total: 7,
@@ -183,11 +557,165 @@ mod tests {
         assert_eq!(pool.retrieve(&uuid), None);
     }
 
+    #[test]
+    fn insert_and_fail() {
+        let mut pool = TaskPool::<u8, EmptyProgress, String>::default();
+        let (handle, uuid) = pool.insert(EmptyProgress {});
+
+        pool.fail(handle, "boom".to_owned());
+
+        assert_eq!(get_inner_size(&pool), 1);
+        assert_eq!(pool.retrieve(&uuid), Some(Failed("boom".to_owned())));
+        assert_eq!(get_inner_size(&pool), 0);
+        assert_eq!(pool.retrieve(&uuid), None);
+    }
+
+    #[test]
+    fn subscribe_receives_progress_and_terminal_state() {
+        let mut pool = TaskPool::<u8, Progress, ()>::default();
+        let (handle, uuid) = pool.insert(Progress {
+            progress: 0,
+            total: 7,
+        });
+
+        let receiver = pool.subscribe(&uuid).unwrap();
+
+        pool.progress(&handle);
+        pool.complete(handle, 42);
+
+        assert_eq!(
+            receiver.recv(),
+            Ok(Pending(Progress {
+                progress: 1,
+                total: 7
+            }))
+        );
+        assert_eq!(receiver.recv(), Ok(Done(42)));
+        assert!(receiver.recv().is_err()); // senders dropped after completion
+    }
+
+    #[test]
+    fn subscribe_to_unknown_task_is_none() {
+        let mut pool = TaskPool::<u8, Progress, ()>::default();
+        assert!(pool.subscribe(&uuid::Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn spawn_drives_task_to_completion() {
+        use std::sync::{Arc, Mutex};
+
+        let pool = Arc::new(Mutex::new(TaskPool::<u8, Progress, String>::default()));
+        let uuid = TaskPool::spawn(
+            &pool,
+            &ThreadExecutor,
+            Progress {
+                progress: 0,
+                total: 3,
+            },
+            |reporter| async move {
+                for _ in 0..3 {
+                    reporter.progress();
+                }
+                Ok(42)
+            },
+        );
+
+        loop {
+            if let Some(Done(value)) = pool.lock().unwrap().retrieve(&uuid) {
+                assert_eq!(value, 42);
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn spawn_fails_task_on_panic() {
+        use std::sync::{Arc, Mutex};
+
+        let pool = Arc::new(Mutex::new(TaskPool::<u8, Progress, String>::default()));
+        let uuid = TaskPool::spawn(
+            &pool,
+            &ThreadExecutor,
+            Progress {
+                progress: 0,
+                total: 1,
+            },
+            |_reporter| async move {
+                if true {
+                    panic!("boom");
+                }
+                Ok(0)
+            },
+        );
+
+        loop {
+            match pool.lock().unwrap().retrieve(&uuid) {
+                Some(Pending(_)) | None => {}
+                Some(state) => {
+                    assert_eq!(state, Failed(String::new()));
+                    break;
+                }
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Minimal [`Executor`] for tests: drives each future to completion on its
+    /// own thread, polling with a no-op waker (the spawned futures make no real
+    /// progress on a waker, so this is sufficient).
+    struct ThreadExecutor;
+
+    impl crate::Executor for ThreadExecutor {
+        fn execute<Fut>(&self, future: Fut)
+        where
+            Fut: std::future::Future<Output = ()> + Send + 'static,
+        {
+            thread::spawn(move || block_on(future));
+        }
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        use std::{
+            pin::pin,
+            task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+        };
+
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        let waker = unsafe { Waker::from_raw(clone(std::ptr::null())) };
+        let mut context = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(value) => return value,
+                Poll::Pending => thread::yield_now(),
+            }
+        }
+    }
+
+    #[test]
+    fn insert_and_cancel() {
+        let mut pool = TaskPool::<u8, EmptyProgress, ()>::default();
+        let (handle, uuid) = pool.insert(EmptyProgress {});
+
+        pool.cancel(handle);
+
+        assert_eq!(get_inner_size(&pool), 1);
+        assert_eq!(pool.retrieve(&uuid), Some(Cancelled));
+        assert_eq!(get_inner_size(&pool), 0);
+        assert_eq!(pool.retrieve(&uuid), None);
+    }
+
     #[test]
     #[cfg(feature = "lifespan")]
     fn exceed_lifespan() {
         let lifespan = Duration::from_millis(10);
-        let mut pool = TaskPool::<(), EmptyProgress>::default().with_lifespan(Some(lifespan));
+        let mut pool = TaskPool::<(), EmptyProgress, ()>::default().with_lifespan(Some(lifespan));
 
         let id = insert_and_complete(&mut pool);
         thread::sleep(lifespan); // exceed time
@@ -200,7 +728,7 @@ mod tests {
     #[cfg(feature = "lifespan")]
     fn within_lifespan() {
         let lifespan = Duration::from_millis(10);
-        let mut pool = TaskPool::<(), EmptyProgress>::default().with_lifespan(Some(lifespan));
+        let mut pool = TaskPool::<(), EmptyProgress, ()>::default().with_lifespan(Some(lifespan));
 
         let id = insert_and_complete(&mut pool);
         insert_and_complete(&mut pool); // trigger purge by completing new task
@@ -208,16 +736,38 @@ mod tests {
         assert_eq!(pool.retrieve(&id), Some(Done(())));
     }
 
-    fn insert_and_complete(pool: &mut TaskPool<(), EmptyProgress>) -> uuid::Uuid {
+    #[test]
+    #[cfg(feature = "lifespan")]
+    fn reaper_purges_without_new_completions() {
+        use std::sync::{Arc, Mutex};
+
+        let lifespan = Duration::from_millis(10);
+        let pool = Arc::new(Mutex::new(
+            TaskPool::<(), EmptyProgress, ()>::default().with_lifespan(Some(lifespan)),
+        ));
+
+        let id = {
+            let mut pool = pool.lock().unwrap();
+            insert_and_complete(&mut pool)
+        };
+
+        let _reaper = TaskPool::spawn_reaper(Arc::clone(&pool), Duration::from_millis(5));
+        thread::sleep(Duration::from_millis(50)); // let the reaper tick past the lifespan
+
+        assert_eq!(pool.lock().unwrap().retrieve(&id), None);
+    }
+
+    fn insert_and_complete(pool: &mut TaskPool<(), EmptyProgress, ()>) -> uuid::Uuid {
         let (handle, id) = pool.insert(EmptyProgress {});
         pool.complete(handle, ());
         id
     }
 
-    fn get_inner_size<V, P>(pool: &TaskPool<V, P>) -> usize
+    fn get_inner_size<V, P, E>(pool: &TaskPool<V, P, E, InMemoryStore<V, P, E>>) -> usize
     where
         P: Progressible,
     {
-        pool.pending.len() + pool.completed.len()
+        let store = &pool.store;
+        store.pending.len() + store.completed.len() + store.failed.len() + store.cancelled.len()
     }
 }